@@ -1,21 +1,34 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use l1x_sdk::{
-    caller_address, contract, contract_owner_address, emit_event_experimental,
+    block_height, block_timestamp, caller_address, contract, contract_owner_address,
+    current_contract_address, emit_event_experimental,
     store::{LookupMap, Vector},
-    types::{Address, U128},
+    types::{Address, Gas, U128},
+    Promise, PromiseResult,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
+/// Gas attached to the `nft_on_transfer` call made against the receiving contract.
+const GAS_FOR_NFT_ON_TRANSFER: Gas = Gas(25_000_000_000_000);
+
+/// Gas attached to the `nft_resolve_transfer` callback on this contract.
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(15_000_000_000_000);
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
 struct OwnerInfo {
     address: Address,
     token_idx: u32,
+    all_tokens_idx: u32,
 }
 
 impl OwnerInfo {
-    pub fn new(address: Address, token_idx: u32) -> Self {
-        Self { address, token_idx }
+    pub fn new(address: Address, token_idx: u32, all_tokens_idx: u32) -> Self {
+        Self {
+            address,
+            token_idx,
+            all_tokens_idx,
+        }
     }
 }
 
@@ -37,9 +50,35 @@ const STORAGE_GET_APPROVED_KEY: &[u8] = b"approved";
 /// Key for the storage of the approval status data.
 const STORAGE_IS_APPROVED_FOR_ALL_KEY: &[u8] = b"approved-all";
 
+/// Key for the storage of per-token metadata.
+const STORAGE_TOKEN_METADATA_KEY: &[u8] = b"token-metadata";
+
+/// Key for the storage of per-token royalty overrides.
+const STORAGE_TOKEN_ROYALTY_KEY: &[u8] = b"token-royalty";
+
+/// Key for the storage of the ordered list of all minted-and-not-burned token ids.
+const STORAGE_ALL_TOKENS_KEY: &[u8] = b"all-tokens";
+
+/// Key for the storage of active Dutch auction listings.
+const STORAGE_AUCTION_KEY: &[u8] = b"auctions";
+
 /// Token Total Supply Configuration
 const L1X_NFT_TOTAL_SUPPLY: u128 = 10_000u128;
 
+/// Maximum combined royalty a `RoyaltyInfo` may configure, in basis points.
+const MAX_ROYALTY_BPS: u16 = 9_000;
+
+/// Maximum number of tokens a single enumeration query may return.
+const MAX_LIMIT: u32 = 50;
+
+/// Maximum number of tokens a single batch mint/transfer call may process.
+const MAX_BATCH_SIZE: u32 = 50;
+
+/// Current on-disk schema version of `NftContract`. Bump this and extend `migrate`
+/// whenever the field layout changes in a way that breaks Borsh compatibility with
+/// already-deployed state.
+const CONTRACT_VERSION: u32 = 2;
+
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct NFTMetadata {
     name: String,
@@ -49,6 +88,136 @@ pub struct NFTMetadata {
     uri: String,
 }
 
+/// An approval's expiration, following the cw721 approval model. An expired
+/// approval is treated as if it were never granted.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum Expiration {
+    AtHeight(u64),
+    AtTime(u64),
+    Never,
+}
+
+impl Expiration {
+    fn is_expired(&self) -> bool {
+        match self {
+            Expiration::AtHeight(height) => block_height() >= *height,
+            Expiration::AtTime(time) => block_timestamp() >= *time,
+            Expiration::Never => false,
+        }
+    }
+}
+
+/// Per-token metadata, following the NEP-177 NFT metadata extension. Unlike
+/// `NFTMetadata`, which describes the contract/collection as a whole, this lets each
+/// minted token carry its own distinct on-chain attributes instead of relying on an
+/// off-chain `{id}.json` convention.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TokenMetadata {
+    title: Option<String>,
+    description: Option<String>,
+    media: Option<String>,
+    media_hash: Option<String>,
+    copies: Option<u64>,
+    issued_at: Option<u64>,
+    extra: Option<String>,
+    reference: Option<String>,
+    reference_hash: Option<String>,
+}
+
+/// Royalty split for a token's secondary sales, following the EIP-2981 /
+/// SNIP-721 royalty model. Values are basis points (1/100th of a percent) and must
+/// sum to at most `MAX_ROYALTY_BPS`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+pub struct RoyaltyInfo {
+    recipients: BTreeMap<Address, u16>,
+}
+
+impl RoyaltyInfo {
+    fn total_bps(&self) -> u128 {
+        self.recipients.values().map(|bps| *bps as u128).sum()
+    }
+}
+
+/// A Dutch auction listing for a single token, following the Starknet-by-Example
+/// NFT Dutch auction design: the price falls linearly from `starting_price` at
+/// `start_time` to `ending_price` at `start_time + duration`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+pub struct AuctionInfo {
+    seller: Address,
+    starting_price: u128,
+    ending_price: u128,
+    start_time: u64,
+    duration: u64,
+}
+
+fn assert_valid_royalty(royalty: &RoyaltyInfo) {
+    assert!(
+        royalty.total_bps() <= MAX_ROYALTY_BPS as u128,
+        "Royalty recipients sum to more than the maximum of {} bps",
+        MAX_ROYALTY_BPS
+    );
+}
+
+/// Splits `sale_price` across `royalty`'s recipients. Every recipient but the
+/// first gets its direct per-recipient cut, `sale_price * bps / 10_000`
+/// (checked); the first recipient takes its own direct cut plus whatever
+/// floor division left on the table, so the sum of payouts equals
+/// `sale_price * total_bps / 10_000` and never exceeds `sale_price`.
+fn split_royalty_payouts(royalty: &RoyaltyInfo, sale_price: u128) -> Vec<(Address, u128)> {
+    if royalty.total_bps() == 0 {
+        return Vec::new();
+    }
+
+    let mut recipients = royalty.recipients.iter();
+    let (first_recipient, _) = recipients.next().expect("royalty has no recipients");
+
+    let mut others = Vec::with_capacity(royalty.recipients.len() - 1);
+    let mut others_total: u128 = 0;
+    for (recipient, bps) in recipients {
+        let cut = sale_price
+            .checked_mul(*bps as u128)
+            .expect("royalty calculation overflow")
+            / 10_000;
+        others_total += cut;
+        others.push((*recipient, cut));
+    }
+
+    let total_cut = sale_price
+        .checked_mul(royalty.total_bps())
+        .expect("royalty calculation overflow")
+        / 10_000;
+
+    let mut result = Vec::with_capacity(others.len() + 1);
+    result.push((*first_recipient, total_cut - others_total));
+    result.extend(others);
+
+    result
+}
+
+/// Splits a settled Dutch-auction sale between the royalty recipients already
+/// paid out in `royalty_payouts`, the seller, and a refund to the buyer for
+/// any amount paid above `price`. Returns `(seller_cut, refund)`.
+fn split_auction_proceeds(
+    price: u128,
+    attached: u128,
+    royalty_payouts: &[(Address, U128)],
+) -> (u128, u128) {
+    let royalty_total = royalty_payouts
+        .iter()
+        .try_fold(0u128, |acc, (_, amount)| acc.checked_add(amount.0))
+        .expect("royalty payout overflow");
+
+    let seller_cut = price
+        .checked_sub(royalty_total)
+        .expect("royalty payouts exceed the sale price");
+
+    let refund = attached
+        .checked_sub(price)
+        .expect("attached deposit less than the auction price");
+
+    (seller_cut, refund)
+}
+
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
 enum NftEvent {
     NftTokenMinted(String),
@@ -56,6 +225,30 @@ enum NftEvent {
     NftTokenApproved(String),
     NftTokenApprovedForAll(String),
     NftTokenTransfered(String),
+    NftTokenTransferReverted(String),
+    NftTokenMetadataUpdated(String),
+    NftTokenApprovalRevoked(String),
+    NftRoyaltySet(String),
+    NftAuctionStarted(String),
+    NftAuctionSettled(String),
+}
+
+/// Arguments passed to the receiving contract's `nft_on_transfer` method, following
+/// the NEP-171 `nft_transfer_call` convention.
+#[derive(Serialize, Deserialize)]
+struct NftOnTransferArgs {
+    sender_id: Address,
+    previous_owner_id: Address,
+    token_id: U128,
+    msg: String,
+}
+
+/// Arguments passed to the `nft_resolve_transfer` callback on this contract.
+#[derive(Serialize, Deserialize)]
+struct NftResolveTransferArgs {
+    from: Address,
+    to: Address,
+    token_id: U128,
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
@@ -65,9 +258,58 @@ pub struct NftContract {
     minted_total: u128,
     balance_of: LookupMap<Address, Vector<u128>>,
     owner_of: LookupMap<u128, OwnerInfo>,
-    get_approved: LookupMap<u128, Address>,
-    is_approved_for_all: LookupMap<Address, BTreeMap<Address, bool>>,
+    get_approved: LookupMap<u128, (Address, Expiration)>,
+    is_approved_for_all: LookupMap<Address, BTreeMap<Address, Expiration>>,
     burned_nfts: BTreeSet<u128>,
+    token_metadata: LookupMap<u128, TokenMetadata>,
+    default_royalty: Option<RoyaltyInfo>,
+    token_royalty: LookupMap<u128, RoyaltyInfo>,
+    all_tokens: Vector<u128>,
+    auctions: LookupMap<u128, AuctionInfo>,
+    /// Schema version of this stored state, so `migrate` can tell which prior
+    /// layout it is upgrading from.
+    version: u32,
+}
+
+/// Mirrors `NftContract`'s field layout at schema version 0: every field of
+/// version 1 except `version`, so `migrate` can deserialize state written by that
+/// version and map it onto the current layout. State written by the older layout
+/// without expiring approvals, token metadata, royalties or `all_tokens` is not
+/// covered by any snapshot; `migrate_from_bytes` panics on it.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct NftContractV0 {
+    metadata: NFTMetadata,
+    current_token_id: u128,
+    minted_total: u128,
+    balance_of: LookupMap<Address, Vector<u128>>,
+    owner_of: LookupMap<u128, OwnerInfo>,
+    get_approved: LookupMap<u128, (Address, Expiration)>,
+    is_approved_for_all: LookupMap<Address, BTreeMap<Address, Expiration>>,
+    burned_nfts: BTreeSet<u128>,
+    token_metadata: LookupMap<u128, TokenMetadata>,
+    default_royalty: Option<RoyaltyInfo>,
+    token_royalty: LookupMap<u128, RoyaltyInfo>,
+    all_tokens: Vector<u128>,
+}
+
+/// Mirrors `NftContract`'s field layout at schema version 1, before the `auctions`
+/// module was added, so `migrate` can deserialize state written by that version and
+/// map it onto the current layout.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct NftContractV1 {
+    metadata: NFTMetadata,
+    current_token_id: u128,
+    minted_total: u128,
+    balance_of: LookupMap<Address, Vector<u128>>,
+    owner_of: LookupMap<u128, OwnerInfo>,
+    get_approved: LookupMap<u128, (Address, Expiration)>,
+    is_approved_for_all: LookupMap<Address, BTreeMap<Address, Expiration>>,
+    burned_nfts: BTreeSet<u128>,
+    token_metadata: LookupMap<u128, TokenMetadata>,
+    default_royalty: Option<RoyaltyInfo>,
+    token_royalty: LookupMap<u128, RoyaltyInfo>,
+    all_tokens: Vector<u128>,
+    version: u32,
 }
 
 #[contract]
@@ -92,6 +334,12 @@ impl NftContract {
             get_approved: LookupMap::new(STORAGE_GET_APPROVED_KEY.to_vec()),
             is_approved_for_all: LookupMap::new(STORAGE_IS_APPROVED_FOR_ALL_KEY.to_vec()),
             burned_nfts: BTreeSet::new(),
+            token_metadata: LookupMap::new(STORAGE_TOKEN_METADATA_KEY.to_vec()),
+            default_royalty: None,
+            token_royalty: LookupMap::new(STORAGE_TOKEN_ROYALTY_KEY.to_vec()),
+            all_tokens: Vector::new(STORAGE_ALL_TOKENS_KEY.to_vec()),
+            auctions: LookupMap::new(STORAGE_AUCTION_KEY.to_vec()),
+            version: CONTRACT_VERSION,
         };
         contract.save();
     }
@@ -131,12 +379,16 @@ impl NftContract {
         contract.minted_total.into()
     }
 
-    pub fn nft_mint_to(to: Address) -> U128 {
+    pub fn nft_mint_to(
+        to: Address,
+        token_metadata: Option<TokenMetadata>,
+        royalty: Option<RoyaltyInfo>,
+    ) -> U128 {
         // load the contract storage state
         let mut contract = Self::load();
 
         // Call the internal implementation
-        let new_token_id = contract.mint_to(to);
+        let new_token_id = contract.mint_to(to, token_metadata, royalty);
 
         // Save the contract state
         contract.save();
@@ -144,12 +396,17 @@ impl NftContract {
         new_token_id.into()
     }
 
-    pub fn nft_mint_id_to(to: Address, id: U128) -> U128 {
+    pub fn nft_mint_id_to(
+        to: Address,
+        id: U128,
+        token_metadata: Option<TokenMetadata>,
+        royalty: Option<RoyaltyInfo>,
+    ) -> U128 {
         // load the contract storage state
         let mut contract = Self::load();
 
         // Call the internal implementation
-        let new_token_id = contract.mint_id_to(to, id.into());
+        let new_token_id = contract.mint_id_to(to, id.into(), token_metadata, royalty);
 
         // Save the contract state
         contract.save();
@@ -157,6 +414,95 @@ impl NftContract {
         new_token_id.into()
     }
 
+    /// Mints `count` tokens to `to` in one call, saving the contract state only
+    /// once instead of once per token. Capped at `MAX_BATCH_SIZE` to bound gas.
+    pub fn nft_batch_mint_to(to: Address, count: u32) -> Vec<U128> {
+        assert!(
+            count > 0 && count <= MAX_BATCH_SIZE,
+            "Batch size must be between 1 and {}",
+            MAX_BATCH_SIZE
+        );
+
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        let mut minted = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            minted.push(contract.mint_to(to, None, None).into());
+        }
+
+        // Save the contract state
+        contract.save();
+
+        minted
+    }
+
+    /// Mints a specific id to each `(to, id)` entry in one call, saving the contract
+    /// state only once instead of once per token. Capped at `MAX_BATCH_SIZE` to
+    /// bound gas.
+    pub fn nft_batch_mint_id_to(entries: Vec<(Address, U128)>) -> Vec<U128> {
+        assert!(
+            !entries.is_empty() && entries.len() <= MAX_BATCH_SIZE as usize,
+            "Batch size must be between 1 and {}",
+            MAX_BATCH_SIZE
+        );
+
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        let mut minted = Vec::with_capacity(entries.len());
+        for (to, id) in entries {
+            minted.push(contract.mint_id_to(to, id.into(), None, None).into());
+        }
+
+        // Save the contract state
+        contract.save();
+
+        minted
+    }
+
+    /// Returns the on-chain metadata stored for `id`, if any was set at mint time or
+    /// via `nft_update_token_metadata`.
+    pub fn nft_token_metadata(id: U128) -> Option<TokenMetadata> {
+        let contract = Self::load();
+        contract.token_metadata.get(&id.into()).cloned()
+    }
+
+    /// Replaces the on-chain metadata for `id`. Only the token's current owner may
+    /// call this.
+    pub fn nft_update_token_metadata(id: U128, metadata: TokenMetadata) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.update_token_metadata(id.into(), metadata);
+
+        // Save the contract state
+        contract.save();
+    }
+
+    /// Returns, for a hypothetical sale of `id` at `sale_price`, the list of
+    /// `(recipient, amount)` pairs a marketplace should pay out from the proceeds.
+    /// Falls back to the contract-wide default royalty if `id` has no override, and
+    /// returns an empty list if neither is configured.
+    pub fn nft_royalty_info(id: U128, sale_price: U128) -> Vec<(Address, U128)> {
+        let contract = Self::load();
+        contract.royalty_info(id.into(), sale_price.into())
+    }
+
+    /// Sets or clears the contract-wide default royalty applied to tokens without a
+    /// per-token override. Only the contract owner may call this.
+    pub fn nft_set_default_royalty(royalty: Option<RoyaltyInfo>) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.set_default_royalty(royalty);
+
+        // Save the contract state
+        contract.save();
+    }
+
     pub fn nft_burn(id: U128) {
         // load the contract storage state
         let mut contract = Self::load();
@@ -168,23 +514,53 @@ impl NftContract {
         contract.save();
     }
 
-    pub fn nft_approve(spender: Address, id: U128) {
+    pub fn nft_approve(spender: Address, id: U128, expires: Option<Expiration>) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.approve(spender, id.into(), expires.unwrap_or(Expiration::Never));
+
+        // Save the contract state
+        contract.save();
+    }
+
+    pub fn nft_set_approval_for_all(
+        operator: Address,
+        approved: bool,
+        expires: Option<Expiration>,
+    ) {
         // load the contract storage state
         let mut contract = Self::load();
 
         // Call the internal implementation
-        contract.approve(spender, id.into());
+        contract.set_approval_for_all(operator, approved, expires.unwrap_or(Expiration::Never));
 
         // Save the contract state
         contract.save();
     }
 
-    pub fn nft_set_approval_for_all(operator: Address, approved: bool) {
+    /// Revokes a previously granted single-token approval. Only the token's owner may
+    /// call this; it is a no-op if `spender` is not the currently approved spender.
+    pub fn nft_revoke(spender: Address, id: U128) {
         // load the contract storage state
         let mut contract = Self::load();
 
         // Call the internal implementation
-        contract.set_approval_for_all(operator, approved);
+        contract.revoke(spender, id.into());
+
+        // Save the contract state
+        contract.save();
+    }
+
+    /// Revokes a previously granted operator-wide approval. Only the caller's own
+    /// operator entry can be revoked.
+    pub fn nft_revoke_all(operator: Address) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.revoke_all(operator);
 
         // Save the contract state
         contract.save();
@@ -201,6 +577,128 @@ impl NftContract {
         contract.save();
     }
 
+    /// Transfers each `(to, id)` entry in `transfers` from `from` in one call, saving
+    /// the contract state only once instead of once per token. Capped at
+    /// `MAX_BATCH_SIZE` to bound gas.
+    pub fn nft_batch_transfer_from(from: Address, transfers: Vec<(Address, U128)>) {
+        assert!(
+            !transfers.is_empty() && transfers.len() <= MAX_BATCH_SIZE as usize,
+            "Batch size must be between 1 and {}",
+            MAX_BATCH_SIZE
+        );
+
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        for (to, id) in transfers {
+            contract.transfer_from(from, to, id.into());
+        }
+
+        // Save the contract state
+        contract.save();
+    }
+
+    /// Transfers `id` from `from` to `to` and then invokes `nft_on_transfer` on `to`,
+    /// so the token can be deposited directly into a marketplace/escrow contract in a
+    /// single atomic call instead of a separate approve+pull flow. If the receiver's
+    /// callback rejects the transfer (returns `true`) or panics, `nft_resolve_transfer`
+    /// reverts the ownership change.
+    pub fn nft_transfer_from_call(from: Address, to: Address, id: U128, msg: String) -> Promise {
+        let sender_id = caller_address();
+
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.transfer_from(from, to, id.into());
+
+        // Save the contract state
+        contract.save();
+
+        let on_transfer_args = NftOnTransferArgs {
+            sender_id,
+            previous_owner_id: from,
+            token_id: id,
+            msg,
+        };
+        let resolve_args = NftResolveTransferArgs {
+            from,
+            to,
+            token_id: id,
+        };
+
+        Promise::new(to)
+            .function_call(
+                "nft_on_transfer".to_string(),
+                serde_json::to_vec(&on_transfer_args).unwrap(),
+                0,
+                GAS_FOR_NFT_ON_TRANSFER,
+            )
+            .then(Promise::new(current_contract_address()).function_call(
+                "nft_resolve_transfer".to_string(),
+                serde_json::to_vec(&resolve_args).unwrap(),
+                0,
+                GAS_FOR_RESOLVE_TRANSFER,
+            ))
+    }
+
+    /// Private callback invoked by `nft_transfer_from_call`'s promise chain. Reverts
+    /// the transfer by re-inserting `id` to `from` if the receiver's `nft_on_transfer`
+    /// callback returned `true` (reject) or panicked, unless `to` no longer owns the
+    /// token (i.e. it was transferred again in the meantime).
+    ///
+    /// Returns `true` if the transfer was reverted, `false` if it was kept.
+    pub fn nft_resolve_transfer(from: Address, to: Address, token_id: U128) -> bool {
+        assert_eq!(
+            caller_address(),
+            current_contract_address(),
+            "nft_resolve_transfer can only be called as a callback by the contract itself"
+        );
+
+        let should_revert = match l1x_sdk::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                serde_json::from_slice::<bool>(&bytes).unwrap_or(true)
+            }
+            _ => true,
+        };
+
+        if !should_revert {
+            return false;
+        }
+
+        let id: u128 = token_id.into();
+        let mut contract = Self::load();
+
+        // Re-check that `to` still owns the token before reverting, to avoid
+        // clobbering a subsequent transfer.
+        let still_owned_by_to = contract
+            .owner_of
+            .get(&id)
+            .map(|owner_info| owner_info.address == to)
+            .unwrap_or(false);
+
+        if !still_owned_by_to {
+            return false;
+        }
+
+        contract.internal_remove_token(id);
+        contract.internal_add_token_to(from, id);
+        contract.save();
+
+        // Emit the transfer reverted event
+        emit_event_experimental(NftEvent::NftTokenTransferReverted(format!(
+            "Reverted transfer of token_id {:#?} from {} back to {}",
+            id, to, from
+        )));
+
+        l1x_sdk::msg(&format!(
+            "Reverted transfer of token_id {:#?} from {} back to {}",
+            id, to, from
+        ));
+
+        true
+    }
+
     pub fn nft_balance_of(owner: Address) -> U128 {
         // load the contract storage state
         let contract = Self::load();
@@ -224,6 +722,102 @@ impl NftContract {
         // Call the internal implementation
         contract.owned_tokens(owner)
     }
+
+    /// Total number of tokens currently in existence (minted minus burned).
+    pub fn nft_total_supply() -> U128 {
+        let contract = Self::load();
+        contract.total_supply().into()
+    }
+
+    /// Pages over every live token id in the order of the global `all_tokens` list.
+    /// That order is not mint order: a transfer moves the id to the end of the list,
+    /// and removing an id moves the last id into its slot. `limit` is capped at
+    /// `MAX_LIMIT` to bound gas on a single call.
+    pub fn nft_tokens(from_index: U128, limit: u32) -> Vec<U128> {
+        let contract = Self::load();
+        contract.tokens(from_index.into(), limit)
+    }
+
+    /// Pages over `owner`'s token ids. `limit` is capped at `MAX_LIMIT` to bound gas
+    /// on a single call.
+    pub fn nft_tokens_for_owner(owner: Address, from_index: U128, limit: u32) -> Vec<U128> {
+        let contract = Self::load();
+        contract.tokens_for_owner(owner, from_index.into(), limit)
+    }
+
+    /// Deploys `code` as the contract's new Wasm. Only the contract owner may call
+    /// this; it does not touch stored state, so `migrate` must be called afterwards
+    /// to bring it onto any new schema the upgraded code expects.
+    pub fn upgrade(code: Vec<u8>) {
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the contract owner can call this method"
+        );
+
+        l1x_sdk::set_contract_code(code);
+    }
+
+    /// Brings the stored state onto the current schema. Idempotent: a no-op if the
+    /// state is already on `CONTRACT_VERSION`. Panics if the contract was never
+    /// initialized.
+    pub fn migrate() {
+        let bytes = l1x_sdk::storage_read(STORAGE_CONTRACT_KEY)
+            .expect("The contract isn't initialized");
+
+        let already_current = Self::try_from_slice(&bytes).is_ok();
+        let mut contract = Self::migrate_from_bytes(&bytes);
+        if !already_current {
+            contract.save();
+        }
+    }
+
+    /// Lists `id` for sale as a Dutch auction: the price falls linearly from
+    /// `starting_price` at `start_time` to `ending_price` once `duration` has
+    /// elapsed. Only the token's current owner may call this.
+    pub fn nft_start_dutch_auction(
+        id: U128,
+        starting_price: U128,
+        ending_price: U128,
+        start_time: u64,
+        duration: u64,
+    ) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.start_dutch_auction(
+            id.into(),
+            starting_price.into(),
+            ending_price.into(),
+            start_time,
+            duration,
+        );
+
+        // Save the contract state
+        contract.save();
+    }
+
+    /// Returns the current price of `id`'s active Dutch auction.
+    pub fn nft_auction_price(id: U128) -> U128 {
+        let contract = Self::load();
+        contract.auction_price(id.into()).into()
+    }
+
+    /// Buys `id` at its current Dutch auction price, paired with an attached L1X
+    /// value transfer. Settles at the computed price, transfers the token to the
+    /// caller, splits the proceeds according to any configured royalties, refunds
+    /// any overpayment, and clears the auction entry.
+    pub fn nft_buy(id: U128) {
+        // load the contract storage state
+        let mut contract = Self::load();
+
+        // Call the internal implementation
+        contract.buy(id.into());
+
+        // Save the contract state
+        contract.save();
+    }
 }
 
 impl NftContract {
@@ -260,9 +854,25 @@ impl NftContract {
             owner_ref.token_idx = owner_info.token_idx;
         }
 
-        //  delete the token_id entry from `owner` and `get_approved` state
+        // Same swap-remove bookkeeping for the global `all_tokens` enumeration list
+        let is_last_in_all_tokens = owner_info.all_tokens_idx == self.all_tokens.len() - 1;
+        self.all_tokens.swap_remove(owner_info.all_tokens_idx);
+        if !is_last_in_all_tokens {
+            let swapped_token_id = self
+                .all_tokens
+                .get(owner_info.all_tokens_idx)
+                .expect("Can't get the swapped token_id");
+            let owner_ref = self
+                .owner_of
+                .get_mut(swapped_token_id)
+                .expect("Can't find an owner of the swapped token_id");
+            owner_ref.all_tokens_idx = owner_info.all_tokens_idx;
+        }
+
+        //  delete the token_id entry from `owner`, `get_approved` and `auctions` state
         self.owner_of.remove(id);
         self.get_approved.remove(id);
+        self.auctions.remove(id);
 
         (owner_info.address, balance_from.len())
     }
@@ -280,12 +890,22 @@ impl NftContract {
         };
 
         balance_to.push(id);
-
         let last_idx = balance_to.len() - 1;
-        self.owner_of.insert(id, OwnerInfo::new(to, last_idx));
+
+        self.all_tokens.push(id);
+        let all_tokens_idx = self.all_tokens.len() - 1;
+
+        self.owner_of
+            .insert(id, OwnerInfo::new(to, last_idx, all_tokens_idx));
     }
 
-    fn mint_id_to(&mut self, to: Address, id: u128) -> u128 {
+    fn mint_id_to(
+        &mut self,
+        to: Address,
+        id: u128,
+        token_metadata: Option<TokenMetadata>,
+        royalty: Option<RoyaltyInfo>,
+    ) -> u128 {
         let new_token_id = id;
         assert!(
             !self.burned_nfts.contains(&id),
@@ -301,6 +921,15 @@ impl NftContract {
 
         self.internal_add_token_to(to, new_token_id);
 
+        if let Some(token_metadata) = token_metadata {
+            self.token_metadata.insert(new_token_id, token_metadata);
+        }
+
+        if let Some(royalty) = royalty {
+            assert_valid_royalty(&royalty);
+            self.token_royalty.insert(new_token_id, royalty);
+        }
+
         self.minted_total += 1;
 
         // Emit the Token minted event
@@ -317,7 +946,12 @@ impl NftContract {
         new_token_id
     }
 
-    fn mint_to(&mut self, to: Address) -> u128 {
+    fn mint_to(
+        &mut self,
+        to: Address,
+        token_metadata: Option<TokenMetadata>,
+        royalty: Option<RoyaltyInfo>,
+    ) -> u128 {
         let mut new_token_id: u128 = self.current_token_id + 1;
 
         // Find the closed available id
@@ -332,11 +966,67 @@ impl NftContract {
 
         self.current_token_id = new_token_id;
 
-        self.mint_id_to(to, new_token_id);
+        self.mint_id_to(to, new_token_id, token_metadata, royalty);
 
         new_token_id
     }
 
+    fn update_token_metadata(&mut self, id: u128, metadata: TokenMetadata) {
+        let owner = self.owner_of(id);
+
+        assert_eq!(
+            caller_address(),
+            owner,
+            "Only the token owner can update its metadata"
+        );
+
+        self.token_metadata.insert(id, metadata);
+
+        // Emit the metadata updated event
+        emit_event_experimental(NftEvent::NftTokenMetadataUpdated(format!(
+            "Metadata updated for token_id {:#?} by Owner {}",
+            id, owner
+        )));
+
+        l1x_sdk::msg(&format!(
+            "Metadata updated for token_id {:#?} by Owner {}",
+            id, owner
+        ));
+    }
+
+    fn royalty_info(&self, id: u128, sale_price: u128) -> Vec<(Address, U128)> {
+        let royalty = match self.token_royalty.get(&id).or(self.default_royalty.as_ref()) {
+            Some(royalty) => royalty,
+            None => return Vec::new(),
+        };
+
+        split_royalty_payouts(royalty, sale_price)
+            .into_iter()
+            .map(|(address, cut)| (address, cut.into()))
+            .collect()
+    }
+
+    fn set_default_royalty(&mut self, royalty: Option<RoyaltyInfo>) {
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the contract owner can call this method"
+        );
+
+        if let Some(royalty) = &royalty {
+            assert_valid_royalty(royalty);
+        }
+
+        emit_event_experimental(NftEvent::NftRoyaltySet(format!(
+            "Default royalty set to {:#?}",
+            royalty
+        )));
+
+        l1x_sdk::msg(&format!("Default royalty set to {:#?}", royalty));
+
+        self.default_royalty = royalty;
+    }
+
     fn burn(&mut self, id: u128) {
         assert_eq!(
             caller_address(),
@@ -354,6 +1044,7 @@ impl NftContract {
 
         // update id to burned_nfts storage
         self.burned_nfts.insert(id);
+        self.token_metadata.remove(id);
         // Emit the Token burned event
         emit_event_experimental(NftEvent::NftTokenBurned(format!(
             "Burn Token_ID {:#?} from Owner {} Balance {:#?}",
@@ -366,7 +1057,7 @@ impl NftContract {
         ));
     }
 
-    fn approve(&mut self, spender: Address, id: u128) {
+    fn approve(&mut self, spender: Address, id: u128, expires: Expiration) {
         // Get the caller Address
         let caller_id = l1x_sdk::caller_address();
 
@@ -384,7 +1075,7 @@ impl NftContract {
             .is_approved_for_all
             .get(&owner.address)
             .and_then(|approved_map| approved_map.get(&caller_id))
-            .copied()
+            .map(|expires| !expires.is_expired())
             .unwrap_or(false);
 
         assert!(
@@ -395,48 +1086,83 @@ impl NftContract {
         );
 
         // Authorize the spender for the given ID
-        self.get_approved.insert(id, spender.clone());
+        self.get_approved.insert(id, (spender.clone(), expires));
 
         // Emit the approval done event
         emit_event_experimental(NftEvent::NftTokenApproved(format!(
-            "Approval done for token_id {:#?} from Owner {} for Spender {}",
-            id, owner.address, spender
+            "Approval done for token_id {:#?} from Owner {} for Spender {} Expires {:#?}",
+            id, owner.address, spender, expires
+        )));
+
+        l1x_sdk::msg(&format!(
+            "Approval done for token_id {:#?} from Owner {} for Spender {} Expires {:#?}",
+            id, owner.address, spender, expires
+        ));
+    }
+
+    fn revoke(&mut self, spender: Address, id: u128) {
+        let owner = self.owner_of(id);
+
+        assert_eq!(
+            caller_address(),
+            owner,
+            "Only the token owner can revoke an approval"
+        );
+
+        if let Some((approved_spender, _)) = self.get_approved.get(&id) {
+            if *approved_spender == spender {
+                self.get_approved.remove(id);
+            }
+        }
+
+        // Emit the approval revoked event
+        emit_event_experimental(NftEvent::NftTokenApprovalRevoked(format!(
+            "Revoked approval for token_id {:#?} of Spender {} by Owner {}",
+            id, spender, owner
         )));
 
         l1x_sdk::msg(&format!(
-            "Approval done for token_id {:#?} from Owner {} for Spender {}",
-            id, owner.address, spender
+            "Revoked approval for token_id {:#?} of Spender {} by Owner {}",
+            id, spender, owner
         ));
     }
 
-    fn set_approval_for_all(&mut self, operator: Address, approved: bool) {
+    fn set_approval_for_all(&mut self, operator: Address, approved: bool, expires: Expiration) {
         // Get the caller Address
         let caller_id = l1x_sdk::caller_address();
 
         // Modify the state of `is_approved_for_all`
-        if let Some(approved_map) = self.is_approved_for_all.get_mut(&caller_id) {
-            // Borrow the value as mutable using `get_mut` and then insert the new key-value pair
-            approved_map.insert(operator.clone(), approved);
-        } else {
-            // If the entry doesn't exist, create a new map, insert the pair, and then insert the new map into `is_approved_for_all`
-            let mut new_approved_map = BTreeMap::new();
-            new_approved_map.insert(operator.clone(), approved);
-            self.is_approved_for_all
-                .insert(caller_id.clone(), new_approved_map);
+        if approved {
+            if let Some(approved_map) = self.is_approved_for_all.get_mut(&caller_id) {
+                // Borrow the value as mutable using `get_mut` and then insert the new key-value pair
+                approved_map.insert(operator.clone(), expires);
+            } else {
+                // If the entry doesn't exist, create a new map, insert the pair, and then insert the new map into `is_approved_for_all`
+                let mut new_approved_map = BTreeMap::new();
+                new_approved_map.insert(operator.clone(), expires);
+                self.is_approved_for_all
+                    .insert(caller_id.clone(), new_approved_map);
+            }
+        } else if let Some(approved_map) = self.is_approved_for_all.get_mut(&caller_id) {
+            approved_map.remove(&operator);
         }
 
         // Emit the approval for All done event
         emit_event_experimental(NftEvent::NftTokenApprovedForAll(format!(
-            "Approval-For-All done from Caller {} Operator {} Approved {:#?}",
-            caller_id, operator, approved
+            "Approval-For-All done from Caller {} Operator {} Approved {:#?} Expires {:#?}",
+            caller_id, operator, approved, expires
         )));
 
         l1x_sdk::msg(&format!(
-            "Approval-For-All done from Caller {} Operator {} Approved {:#?}",
-            caller_id, operator, approved
+            "Approval-For-All done from Caller {} Operator {} Approved {:#?} Expires {:#?}",
+            caller_id, operator, approved, expires
         ));
     }
 
+    fn revoke_all(&mut self, operator: Address) {
+        self.set_approval_for_all(operator, false, Expiration::Never);
+    }
+
     fn transfer_from(&mut self, from: Address, to: Address, id: u128) {
         let caller_id = l1x_sdk::caller_address();
 
@@ -464,12 +1190,14 @@ impl NftContract {
             self.is_approved_for_all
                 .get(&from)
                 .and_then(|approved_map| approved_map.get(&caller_id))
-                .copied()
+                .map(|expires| !expires.is_expired())
                 .unwrap_or(false)
         };
         let is_approved_spender = {
-            let spender_id = self.get_approved.get(&id);
-            spender_id == Some(&caller_id)
+            match self.get_approved.get(&id) {
+                Some((spender_id, expires)) => spender_id == &caller_id && !expires.is_expired(),
+                None => false,
+            }
         };
 
         assert!(caller_is_owner || is_approved_operator || is_approved_spender,
@@ -526,6 +1254,174 @@ impl NftContract {
         result
     }
 
+    fn total_supply(&self) -> u128 {
+        self.minted_total - self.burned_nfts.len() as u128
+    }
+
+    fn tokens(&self, from_index: u128, limit: u32) -> Vec<U128> {
+        let limit = limit.min(MAX_LIMIT);
+        let start = u32::try_from(from_index).unwrap_or(u32::MAX);
+        let end = start.saturating_add(limit).min(self.all_tokens.len());
+
+        let mut result = Vec::with_capacity((end.saturating_sub(start)) as usize);
+        for idx in start..end {
+            result.push((*self.all_tokens.get(idx).expect("token id within bounds")).into())
+        }
+
+        result
+    }
+
+    fn tokens_for_owner(&self, owner: Address, from_index: u128, limit: u32) -> Vec<U128> {
+        let issued_tokens = match self.balance_of.get(&owner) {
+            Some(issued_tokens) => issued_tokens,
+            None => return Vec::new(),
+        };
+
+        let limit = limit.min(MAX_LIMIT);
+        let start = u32::try_from(from_index).unwrap_or(u32::MAX);
+        let end = start.saturating_add(limit).min(issued_tokens.len());
+
+        let mut result = Vec::with_capacity((end.saturating_sub(start)) as usize);
+        for idx in start..end {
+            result.push(issued_tokens.get(idx).copied().unwrap().into())
+        }
+
+        result
+    }
+
+    fn start_dutch_auction(
+        &mut self,
+        id: u128,
+        starting_price: u128,
+        ending_price: u128,
+        start_time: u64,
+        duration: u64,
+    ) {
+        let seller = self.owner_of(id);
+
+        assert_eq!(
+            caller_address(),
+            seller,
+            "Only the token owner can start an auction"
+        );
+        assert!(duration > 0, "Auction duration must be greater than zero");
+        assert!(
+            starting_price >= ending_price,
+            "Starting price must be greater than or equal to the ending price"
+        );
+
+        let auction = AuctionInfo {
+            seller,
+            starting_price,
+            ending_price,
+            start_time,
+            duration,
+        };
+        self.auctions.insert(id, auction);
+
+        emit_event_experimental(NftEvent::NftAuctionStarted(format!(
+            "Started Dutch auction for token_id {:#?} by Seller {} from {} to {}",
+            id, seller, starting_price, ending_price
+        )));
+
+        l1x_sdk::msg(&format!(
+            "Started Dutch auction for token_id {:#?} by Seller {} from {} to {}",
+            id, seller, starting_price, ending_price
+        ));
+    }
+
+    fn dutch_auction_price(auction: &AuctionInfo, now: u64) -> u128 {
+        if now <= auction.start_time {
+            return auction.starting_price;
+        }
+
+        let elapsed = now - auction.start_time;
+        if elapsed >= auction.duration {
+            return auction.ending_price;
+        }
+
+        let price_drop = auction
+            .starting_price
+            .checked_sub(auction.ending_price)
+            .expect("ending price greater than starting price");
+        let elapsed_drop = price_drop
+            .checked_mul(elapsed as u128)
+            .expect("auction price calculation overflow")
+            .checked_div(auction.duration as u128)
+            .expect("auction duration is zero");
+
+        auction
+            .starting_price
+            .checked_sub(elapsed_drop)
+            .expect("auction price calculation underflow")
+    }
+
+    fn auction_price(&self, id: u128) -> u128 {
+        let auction = self
+            .auctions
+            .get(&id)
+            .unwrap_or_else(|| panic!("No active auction for token {}", id));
+
+        Self::dutch_auction_price(auction, block_timestamp())
+    }
+
+    fn buy(&mut self, id: u128) {
+        let auction = self
+            .auctions
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| panic!("No active auction for token {}", id));
+
+        let seller = self.owner_of(id);
+        assert_eq!(
+            seller, auction.seller,
+            "Auction seller no longer owns the token"
+        );
+
+        let price = Self::dutch_auction_price(&auction, block_timestamp());
+
+        let attached = l1x_sdk::attached_deposit();
+        assert!(
+            attached >= price,
+            "Attached deposit {} is less than the auction price {}",
+            attached,
+            price
+        );
+
+        let buyer = caller_address();
+
+        self.internal_remove_token(id);
+        self.internal_add_token_to(buyer.clone(), id);
+
+        let royalty_payouts = self.royalty_info(id, price);
+        let (seller_cut, refund) = split_auction_proceeds(price, attached, &royalty_payouts);
+
+        for (recipient, amount) in &royalty_payouts {
+            let amount = amount.0;
+            if amount > 0 {
+                Promise::new(recipient.clone()).transfer(amount);
+            }
+        }
+
+        if seller_cut > 0 {
+            Promise::new(seller.clone()).transfer(seller_cut);
+        }
+
+        if refund > 0 {
+            Promise::new(buyer.clone()).transfer(refund);
+        }
+
+        emit_event_experimental(NftEvent::NftAuctionSettled(format!(
+            "Settled Dutch auction for token_id {:#?} Seller {} Buyer {} Price {}",
+            id, seller, buyer, price
+        )));
+
+        l1x_sdk::msg(&format!(
+            "Settled Dutch auction for token_id {:#?} Seller {} Buyer {} Price {}",
+            id, seller, buyer, price
+        ));
+    }
+
     fn load() -> Self {
         match l1x_sdk::storage_read(STORAGE_CONTRACT_KEY) {
             Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
@@ -533,7 +1429,251 @@ impl NftContract {
         }
     }
 
+    /// Maps `bytes` onto the current schema, trying the current layout first
+    /// and then falling back through older snapshot layouts in reverse
+    /// chronological order. Pure mapping logic for `migrate`; does not touch
+    /// storage itself.
+    fn migrate_from_bytes(bytes: &[u8]) -> Self {
+        if let Ok(current) = Self::try_from_slice(bytes) {
+            return current;
+        }
+
+        if let Ok(old) = NftContractV1::try_from_slice(bytes) {
+            return Self {
+                metadata: old.metadata,
+                current_token_id: old.current_token_id,
+                minted_total: old.minted_total,
+                balance_of: old.balance_of,
+                owner_of: old.owner_of,
+                get_approved: old.get_approved,
+                is_approved_for_all: old.is_approved_for_all,
+                burned_nfts: old.burned_nfts,
+                token_metadata: old.token_metadata,
+                default_royalty: old.default_royalty,
+                token_royalty: old.token_royalty,
+                all_tokens: old.all_tokens,
+                auctions: LookupMap::new(STORAGE_AUCTION_KEY.to_vec()),
+                version: CONTRACT_VERSION,
+            };
+        }
+
+        let old = NftContractV0::try_from_slice(bytes)
+            .expect("Stored state doesn't match any known schema");
+
+        Self {
+            metadata: old.metadata,
+            current_token_id: old.current_token_id,
+            minted_total: old.minted_total,
+            balance_of: old.balance_of,
+            owner_of: old.owner_of,
+            get_approved: old.get_approved,
+            is_approved_for_all: old.is_approved_for_all,
+            burned_nfts: old.burned_nfts,
+            token_metadata: old.token_metadata,
+            default_royalty: old.default_royalty,
+            token_royalty: old.token_royalty,
+            all_tokens: old.all_tokens,
+            auctions: LookupMap::new(STORAGE_AUCTION_KEY.to_vec()),
+            version: CONTRACT_VERSION,
+        }
+    }
+
     fn save(&mut self) {
         l1x_sdk::storage_write(STORAGE_CONTRACT_KEY, &self.try_to_vec().unwrap());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn royalty(recipients: &[(Address, u16)]) -> RoyaltyInfo {
+        RoyaltyInfo {
+            recipients: recipients.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn split_royalty_payouts_matches_direct_per_recipient_formula() {
+        // Regression test for the double-rounding bug fixed in commit
+        // c904676: re-splitting an already-rounded aggregate handed the
+        // 0.01%-share recipient the same payout as the ~90%-share recipient.
+        let low = Address([1u8; 20]);
+        let high = Address([2u8; 20]);
+        let r = royalty(&[(low, 1), (high, 8999)]);
+
+        let payouts = split_royalty_payouts(&r, 3);
+
+        let low_cut = payouts.iter().find(|(a, _)| *a == low).unwrap().1;
+        let high_cut = payouts.iter().find(|(a, _)| *a == high).unwrap().1;
+        assert_eq!(low_cut, 0);
+        assert_eq!(high_cut, 2);
+    }
+
+    #[test]
+    fn split_royalty_payouts_single_recipient_gets_full_cut() {
+        let recipient = Address([1u8; 20]);
+        let r = royalty(&[(recipient, 500)]);
+        assert_eq!(split_royalty_payouts(&r, 1_000), vec![(recipient, 50)]);
+    }
+
+    #[test]
+    fn split_royalty_payouts_zero_bps_returns_empty() {
+        let recipient = Address([1u8; 20]);
+        let r = royalty(&[(recipient, 0)]);
+        assert!(split_royalty_payouts(&r, 1_000).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "royalty calculation overflow")]
+    fn split_royalty_payouts_overflow_panics() {
+        let recipient = Address([1u8; 20]);
+        let r = royalty(&[(recipient, 9_000)]);
+        split_royalty_payouts(&r, u128::MAX);
+    }
+
+    fn test_auction(starting_price: u128, ending_price: u128) -> AuctionInfo {
+        AuctionInfo {
+            seller: Address([1u8; 20]),
+            starting_price,
+            ending_price,
+            start_time: 1_000,
+            duration: 100,
+        }
+    }
+
+    #[test]
+    fn dutch_auction_price_at_start_is_starting_price() {
+        let auction = test_auction(100, 10);
+        assert_eq!(NftContract::dutch_auction_price(&auction, 1_000), 100);
+    }
+
+    #[test]
+    fn dutch_auction_price_mid_auction_interpolates_linearly() {
+        let auction = test_auction(100, 0);
+        assert_eq!(NftContract::dutch_auction_price(&auction, 1_050), 50);
+    }
+
+    #[test]
+    fn dutch_auction_price_after_duration_is_ending_price() {
+        let auction = test_auction(100, 10);
+        assert_eq!(NftContract::dutch_auction_price(&auction, 1_500), 10);
+        assert_eq!(NftContract::dutch_auction_price(&auction, 5_000), 10);
+    }
+
+    #[test]
+    fn split_auction_proceeds_no_royalties_pays_seller_in_full() {
+        let (seller_cut, refund) = split_auction_proceeds(100, 120, &[]);
+        assert_eq!(seller_cut, 100);
+        assert_eq!(refund, 20);
+    }
+
+    #[test]
+    fn split_auction_proceeds_splits_royalty_out_of_seller_cut() {
+        let recipient = Address([1u8; 20]);
+        let payouts = vec![(recipient, U128(10))];
+        let (seller_cut, refund) = split_auction_proceeds(100, 100, &payouts);
+        assert_eq!(seller_cut, 90);
+        assert_eq!(refund, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "royalty payouts exceed the sale price")]
+    fn split_auction_proceeds_royalty_exceeding_price_panics() {
+        let recipient = Address([1u8; 20]);
+        let payouts = vec![(recipient, U128(200))];
+        split_auction_proceeds(100, 100, &payouts);
+    }
+
+    fn test_metadata() -> NFTMetadata {
+        NFTMetadata {
+            name: "Test".to_string(),
+            decimals: 0,
+            symbol: "TST".to_string(),
+            icon: None,
+            uri: "https://example.com/".to_string(),
+        }
+    }
+
+    fn v0_fixture() -> NftContractV0 {
+        NftContractV0 {
+            metadata: test_metadata(),
+            current_token_id: 7,
+            minted_total: 3,
+            balance_of: LookupMap::new(STORAGE_BALANCE_OF_KEY.to_vec()),
+            owner_of: LookupMap::new(STORAGE_OWNER_OF_KEY.to_vec()),
+            get_approved: LookupMap::new(STORAGE_GET_APPROVED_KEY.to_vec()),
+            is_approved_for_all: LookupMap::new(STORAGE_IS_APPROVED_FOR_ALL_KEY.to_vec()),
+            burned_nfts: BTreeSet::from([2u128]),
+            token_metadata: LookupMap::new(STORAGE_TOKEN_METADATA_KEY.to_vec()),
+            default_royalty: None,
+            token_royalty: LookupMap::new(STORAGE_TOKEN_ROYALTY_KEY.to_vec()),
+            all_tokens: Vector::new(STORAGE_ALL_TOKENS_KEY.to_vec()),
+        }
+    }
+
+    #[test]
+    fn migrate_from_bytes_maps_v0_schema_onto_current_layout() {
+        let bytes = v0_fixture().try_to_vec().unwrap();
+
+        let migrated = NftContract::migrate_from_bytes(&bytes);
+
+        assert_eq!(migrated.current_token_id, 7);
+        assert_eq!(migrated.minted_total, 3);
+        assert_eq!(migrated.burned_nfts, BTreeSet::from([2u128]));
+        assert_eq!(migrated.version, CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn migrate_from_bytes_maps_v1_schema_onto_current_layout() {
+        let v0 = v0_fixture();
+        let old = NftContractV1 {
+            metadata: v0.metadata,
+            current_token_id: v0.current_token_id,
+            minted_total: v0.minted_total,
+            balance_of: v0.balance_of,
+            owner_of: v0.owner_of,
+            get_approved: v0.get_approved,
+            is_approved_for_all: v0.is_approved_for_all,
+            burned_nfts: v0.burned_nfts,
+            token_metadata: v0.token_metadata,
+            default_royalty: v0.default_royalty,
+            token_royalty: v0.token_royalty,
+            all_tokens: v0.all_tokens,
+            version: 1,
+        };
+        let bytes = old.try_to_vec().unwrap();
+
+        let migrated = NftContract::migrate_from_bytes(&bytes);
+
+        assert_eq!(migrated.current_token_id, 7);
+        assert_eq!(migrated.minted_total, 3);
+        assert_eq!(migrated.version, CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn migrate_from_bytes_is_idempotent_on_current_schema() {
+        let current = NftContract {
+            metadata: test_metadata(),
+            current_token_id: 9,
+            minted_total: 9,
+            balance_of: LookupMap::new(STORAGE_BALANCE_OF_KEY.to_vec()),
+            owner_of: LookupMap::new(STORAGE_OWNER_OF_KEY.to_vec()),
+            get_approved: LookupMap::new(STORAGE_GET_APPROVED_KEY.to_vec()),
+            is_approved_for_all: LookupMap::new(STORAGE_IS_APPROVED_FOR_ALL_KEY.to_vec()),
+            burned_nfts: BTreeSet::new(),
+            token_metadata: LookupMap::new(STORAGE_TOKEN_METADATA_KEY.to_vec()),
+            default_royalty: None,
+            token_royalty: LookupMap::new(STORAGE_TOKEN_ROYALTY_KEY.to_vec()),
+            all_tokens: Vector::new(STORAGE_ALL_TOKENS_KEY.to_vec()),
+            auctions: LookupMap::new(STORAGE_AUCTION_KEY.to_vec()),
+            version: CONTRACT_VERSION,
+        };
+        let bytes = current.try_to_vec().unwrap();
+
+        let migrated = NftContract::migrate_from_bytes(&bytes);
+
+        assert_eq!(migrated.current_token_id, 9);
+        assert_eq!(migrated.version, CONTRACT_VERSION);
+    }
+}